@@ -0,0 +1,70 @@
+//! Automatic mixed precision for the forward pass.
+use crate::Kind;
+use std::cell::Cell;
+
+thread_local! {
+    static AUTOCAST_ENABLED: Cell<bool> = Cell::new(false);
+}
+
+/// Returns whether autocast is currently enabled for the calling thread.
+///
+/// Op wrappers that support mixed precision consult this (through
+/// `autocast_kind`) to decide whether to cast their floating point inputs
+/// to `f16` while keeping the underlying fp32 master weights untouched.
+/// [`super::Conv1D`] is the only such wrapper; other ops, matmul included,
+/// run unchanged, and `bf16` is never selected.
+pub fn is_autocast_enabled() -> bool {
+    AUTOCAST_ENABLED.with(|enabled| enabled.get())
+}
+
+/// Returns the dtype an autocast-eligible op should run in for an input of
+/// `input_kind`, or `None` if the op should run unchanged (autocast is off,
+/// or `input_kind` is not an autocast-eligible floating point type).
+///
+/// This is the actual cast consulted by [`super::Conv1D`]: it calls it, and
+/// if it returns `Some(kind)` it casts its input and its own (fp32 master)
+/// weights to `kind` for the duration of the op only, then casts the output
+/// back.
+pub(crate) fn autocast_kind(input_kind: Kind) -> Option<Kind> {
+    if is_autocast_enabled() && input_kind == Kind::Float {
+        Some(Kind::Half)
+    } else {
+        None
+    }
+}
+
+/// A scoped guard that enables autocast for its lifetime.
+///
+/// Eligible ops run in a lower precision (`f16`) for the duration of
+/// the guard while the stored, trainable weights remain fp32. Dropping the
+/// guard restores the previous autocast state, so nested `Autocast` scopes
+/// compose correctly.
+pub struct Autocast {
+    previous: bool,
+}
+
+impl Autocast {
+    /// Enables (or explicitly disables) autocast for the current thread
+    /// until the returned guard is dropped.
+    pub fn new(enabled: bool) -> Autocast {
+        let previous = AUTOCAST_ENABLED.with(|cell| cell.replace(enabled));
+        Autocast { previous }
+    }
+}
+
+impl Drop for Autocast {
+    fn drop(&mut self) {
+        AUTOCAST_ENABLED.with(|cell| cell.set(self.previous));
+    }
+}
+
+/// Runs `f` with autocast enabled or disabled as given by `enabled`,
+/// restoring the previous state afterwards.
+///
+/// ```ignore
+/// let (critic, actor) = nn::autocast(true, || model(&xs));
+/// ```
+pub fn autocast<T>(enabled: bool, f: impl FnOnce() -> T) -> T {
+    let _guard = Autocast::new(enabled);
+    f()
+}
@@ -0,0 +1,115 @@
+//! A one dimensional convolution layer.
+use crate::Tensor;
+use std::borrow::Borrow;
+
+use super::autocast::autocast_kind;
+
+/// Configuration for a convolution layer operating on a single spatial dimension.
+#[derive(Debug, Clone, Copy)]
+pub struct ConvConfig1D {
+    pub stride: i64,
+    pub padding: i64,
+    pub dilation: i64,
+    pub groups: i64,
+    pub bias: bool,
+    pub ws_init: super::Init,
+    pub bs_init: super::Init,
+}
+
+impl Default for ConvConfig1D {
+    fn default() -> Self {
+        ConvConfig1D {
+            stride: 1,
+            padding: 0,
+            dilation: 1,
+            groups: 1,
+            bias: true,
+            ws_init: super::Init::KaimingUniform,
+            bs_init: super::Init::Const(0.),
+        }
+    }
+}
+
+/// A one dimensional convolution layer.
+#[derive(Debug)]
+pub struct Conv1D {
+    ws: Tensor,
+    bs: Option<Tensor>,
+    stride: i64,
+    padding: i64,
+    dilation: i64,
+    groups: i64,
+}
+
+impl Conv1D {
+    /// Creates a new convolution layer, reading and writing its variables
+    /// (weight, and optionally bias) through `vs`.
+    ///
+    /// The input is expected to have shape `[N, Cin, L]`, the weight has
+    /// shape `[Cout, Cin / groups, kernel_size]` and the output has shape
+    /// `[N, Cout, Lout]` with
+    /// `Lout = (L + 2 * padding - dilation * (kernel_size - 1) - 1) / stride + 1`.
+    pub fn new<'a, T: Borrow<super::Path<'a>>>(
+        vs: T,
+        in_dim: i64,
+        out_dim: i64,
+        kernel_size: i64,
+        config: ConvConfig1D,
+    ) -> Conv1D {
+        let vs = vs.borrow();
+        let bs = if config.bias {
+            Some(vs.var("bias", &[out_dim], config.bs_init))
+        } else {
+            None
+        };
+        let ws = vs.var(
+            "weight",
+            &[out_dim, in_dim / config.groups, kernel_size],
+            config.ws_init,
+        );
+        Conv1D {
+            ws,
+            bs,
+            stride: config.stride,
+            padding: config.padding,
+            dilation: config.dilation,
+            groups: config.groups,
+        }
+    }
+}
+
+impl super::module::Module for Conv1D {
+    fn forward(&self, xs: &Tensor) -> Tensor {
+        // Under an `Autocast` scope, run the convolution itself in the
+        // lower-precision dtype while `self.ws`/`self.bs` stay at their
+        // fp32 master precision; only the local casts below are low
+        // precision, so the stored weights are never touched.
+        match autocast_kind(xs.kind()) {
+            Some(kind) => {
+                let out_kind = xs.kind();
+                let xs = xs.to_kind(kind);
+                let ws = self.ws.to_kind(kind);
+                let bs = self.bs.as_ref().map(|bs| bs.to_kind(kind));
+                Tensor::conv1d(
+                    &xs,
+                    &ws,
+                    bs.as_ref(),
+                    &[self.stride],
+                    &[self.padding],
+                    &[self.dilation],
+                    self.groups,
+                )
+                .to_kind(out_kind)
+            }
+            None => Tensor::conv1d(
+                xs,
+                &self.ws,
+                self.bs.as_ref(),
+                &[self.stride],
+                &[self.padding],
+                &[self.dilation],
+                self.groups,
+            ),
+        }
+    }
+}
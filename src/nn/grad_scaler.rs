@@ -0,0 +1,76 @@
+//! Dynamic loss scaling for mixed precision training.
+use super::Optimizer;
+use crate::Tensor;
+use std::cell::Cell;
+
+/// Multiplies the loss before the backward pass and unscales the resulting
+/// gradients before the optimizer step, dynamically adjusting the scale
+/// factor to avoid `f16`/`bf16` gradient underflow without overflowing into
+/// `inf`/`NaN`.
+#[derive(Debug)]
+pub struct GradScaler {
+    scale: Cell<f64>,
+    growth_factor: f64,
+    backoff_factor: f64,
+    growth_interval: i64,
+    max_scale: f64,
+    healthy_steps: Cell<i64>,
+}
+
+impl GradScaler {
+    /// Creates a new scaler starting at `init_scale`, doubling after
+    /// `growth_interval` consecutive healthy steps (capped at `max_scale`)
+    /// and halving (down to no less than `1.0`) whenever a step's gradients
+    /// contain `inf`/`NaN`.
+    pub fn new(init_scale: f64, growth_interval: i64, max_scale: f64) -> GradScaler {
+        GradScaler {
+            scale: Cell::new(init_scale),
+            growth_factor: 2.0,
+            backoff_factor: 0.5,
+            growth_interval,
+            max_scale,
+            healthy_steps: Cell::new(0),
+        }
+    }
+
+    /// The current loss scale `S`.
+    pub fn scale(&self) -> f64 {
+        self.scale.get()
+    }
+
+    /// Scales `loss`, runs the backward pass, unscales the gradients and
+    /// applies an optimizer step unless the unscaled gradients contain an
+    /// `inf`/`NaN`, in which case the step is skipped and the scale is
+    /// halved, though never below `1.0`. After `growth_interval` consecutive healthy steps the scale
+    /// is doubled, up to `max_scale`.
+    pub fn backward_step(&self, opt: &mut Optimizer, loss: &Tensor) {
+        opt.zero_grad();
+        (loss * self.scale.get()).backward();
+        opt.unscale_grads(1.0 / self.scale.get());
+
+        if opt.has_inf_or_nan_grad() {
+            self.scale.set((self.scale.get() * self.backoff_factor).max(1.0));
+            self.healthy_steps.set(0);
+            return;
+        }
+
+        opt.step();
+
+        let healthy_steps = self.healthy_steps.get() + 1;
+        if healthy_steps >= self.growth_interval {
+            self.scale
+                .set((self.scale.get() * self.growth_factor).min(self.max_scale));
+            self.healthy_steps.set(0);
+        } else {
+            self.healthy_steps.set(healthy_steps);
+        }
+    }
+}
+
+impl Default for GradScaler {
+    /// Starts at a scale of `2^16`, grows every `2000` healthy steps, capped
+    /// at `2^24`, matching common defaults for fp16 mixed precision training.
+    fn default() -> GradScaler {
+        GradScaler::new(65536.0, 2000, 16777216.0)
+    }
+}
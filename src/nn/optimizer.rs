@@ -0,0 +1,110 @@
+//! Optimizers to be used for gradient-descent based training.
+use super::var_store::VarStore;
+use crate::wrappers::optimizer::COptimizer;
+use crate::{TchError, Tensor};
+
+/// An optimizer to run gradient descent.
+#[derive(Debug)]
+pub struct Optimizer {
+    opt: COptimizer,
+    variables: VarStore,
+}
+
+/// Trait for the configuration of an optimizer, e.g. [`Sgd`] or [`Adam`].
+pub trait OptimizerConfig
+where
+    Self: std::marker::Sized,
+{
+    fn build_copt(&self, lr: f64) -> Result<COptimizer, TchError>;
+
+    /// Builds an optimizer with the specified learning rate handling variables stored in `vs`.
+    fn build(self, vs: &VarStore, lr: f64) -> Result<Optimizer, TchError> {
+        let opt = self.build_copt(lr)?;
+        Ok(Optimizer {
+            opt,
+            variables: vs.shallow_clone(),
+        })
+    }
+}
+
+impl Optimizer {
+    /// Zeroes out the gradient for all the trainable tensors.
+    pub fn zero_grad(&self) {
+        self.opt.zero_grad()
+    }
+
+    /// Applies a single optimization step, updating the tracked tensors
+    /// based on their gradients.
+    pub fn step(&self) {
+        self.opt.step()
+    }
+
+    /// Performs a backward step, i.e. zeroes the gradients, computes the
+    /// backward pass for `loss`, and applies a single optimization step.
+    pub fn backward_step(&mut self, loss: &Tensor) {
+        self.zero_grad();
+        loss.backward();
+        self.step();
+    }
+
+    /// Clamps every trainable tensor's gradient element-wise to `[-max, max]`.
+    pub fn clip_grad_value(&self, max: f64) {
+        for var in self.variables.trainable_variables() {
+            let _ = var.grad().clamp_(-max, max);
+        }
+    }
+
+    /// Rescales all trainable tensors' gradients so that their combined
+    /// (global) L2 norm does not exceed `max_norm`.
+    ///
+    /// This computes `total_norm = sqrt(sum(grad.pow(2).sum()))` over every
+    /// trainable tensor's gradient and, if `total_norm > max_norm`, scales
+    /// every gradient in place by `max_norm / (total_norm + 1e-6)`.
+    pub fn clip_grad_norm(&self, max_norm: f64) {
+        let variables = self.variables.trainable_variables();
+        let total_norm = variables
+            .iter()
+            .map(|var| f64::from(var.grad().pow(2.0).sum()))
+            .sum::<f64>()
+            .sqrt();
+        if total_norm > max_norm {
+            let scale = max_norm / (total_norm + 1e-6);
+            for var in variables.iter() {
+                let _ = var.grad().mul_(scale);
+            }
+        }
+    }
+
+    /// Performs a backward step clipping the global gradient norm to
+    /// `max_norm` before applying the optimization step. This is a
+    /// convenience wrapper around [`Optimizer::clip_grad_norm`] that is
+    /// handy for policy-gradient and RNN training, where unclipped
+    /// gradients regularly blow up.
+    pub fn backward_step_clip(&mut self, loss: &Tensor, max_norm: f64) {
+        self.zero_grad();
+        loss.backward();
+        self.clip_grad_norm(max_norm);
+        self.step();
+    }
+
+    /// Multiplies every trainable tensor's gradient by `inv_scale` in place.
+    ///
+    /// Used by [`super::GradScaler`] to bring gradients computed from a
+    /// scaled loss back to their unscaled magnitude before the optimizer
+    /// step (or the inf/NaN check that may precede it).
+    pub(crate) fn unscale_grads(&self, inv_scale: f64) {
+        for var in self.variables.trainable_variables() {
+            let _ = var.grad().mul_(inv_scale);
+        }
+    }
+
+    /// Returns `true` if any trainable tensor's gradient contains an `inf`
+    /// or `NaN` value, used by [`super::GradScaler`] to decide whether to
+    /// skip an optimizer step.
+    pub(crate) fn has_inf_or_nan_grad(&self) -> bool {
+        self.variables
+            .trainable_variables()
+            .iter()
+            .any(|var| !bool::from(var.grad().isfinite().all()))
+    }
+}
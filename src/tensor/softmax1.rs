@@ -0,0 +1,28 @@
+//! The "quiet softmax" variant, softmax1, used by attention layers and
+//! entropy-regularized policies that need to be able to attend to nothing.
+use super::Tensor;
+
+impl Tensor {
+    /// Computes `softmax1(x)_i = exp(x_i) / (1 + sum_j exp(x_j))` along `dim`.
+    ///
+    /// Unlike the regular softmax, the implicit extra "zero logit" in the
+    /// denominator lets a row produce an all-small distribution instead of
+    /// being forced to sum to one, which is useful for attention heads that
+    /// may want to attend to nothing.
+    pub fn softmax1(&self, dim: i64) -> Tensor {
+        self.log_softmax1(dim).exp()
+    }
+
+    /// The numerically stable log of [`Tensor::softmax1`].
+    ///
+    /// Subtracts the per-row max `m` before exponentiating so that the
+    /// implicit zero logit becomes `exp(0 - m) = exp(-m)` in the
+    /// denominator, mirroring how the regular `log_softmax` avoids
+    /// overflow.
+    pub fn log_softmax1(&self, dim: i64) -> Tensor {
+        let m = self.max1(dim, true).0;
+        let shifted = self - &m;
+        let log_denom = ((-&m).exp() + shifted.exp().sum2(&[dim], true)).log();
+        shifted - log_denom
+    }
+}
@@ -0,0 +1,288 @@
+//! Composable data-augmentation transforms for training pipelines.
+//!
+//! Transforms operate on `[channel, height, width]` tensors, and broadcast
+//! the same way over a batched `[batch, channel, height, width]` tensor.
+use crate::nn::Module;
+use crate::Tensor;
+use std::cell::RefCell;
+
+/// A small, seedable xorshift64 generator so that augmentations are
+/// reproducible across runs without pulling in an external RNG crate.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng(if seed == 0 { 0x9E3779B97F4A7C15 } else { seed })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform sample in `[lo, hi)`.
+    fn uniform(&mut self, lo: f64, hi: f64) -> f64 {
+        let frac = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        lo + frac * (hi - lo)
+    }
+}
+
+/// A single augmentation step.
+pub trait Transform: std::fmt::Debug {
+    fn augment(&self, xs: &Tensor, rng: &mut Rng) -> Tensor;
+}
+
+/// Randomly crops a scaled region of the input and resizes it back to
+/// `size x size`, as used to augment classification training sets.
+#[derive(Debug)]
+pub struct RandomResizedCrop {
+    size: i64,
+    scale: (f64, f64),
+    ratio: (f64, f64),
+}
+
+impl RandomResizedCrop {
+    pub fn new(size: i64, scale: (f64, f64), ratio: (f64, f64)) -> RandomResizedCrop {
+        RandomResizedCrop { size, scale, ratio }
+    }
+}
+
+impl RandomResizedCrop {
+    /// Crops and resizes a single `[channel, height, width]` sample.
+    ///
+    /// `resize_filter` only supports 3-D `[channel, height, width]` tensors
+    /// (it round-trips through a `[height, width, channel]` layout), so a
+    /// batched input is handled by calling this once per sample instead.
+    fn augment_one(&self, xs: &Tensor, rng: &mut Rng) -> Tensor {
+        let dims = xs.size();
+        let (h, w) = (dims[dims.len() - 2], dims[dims.len() - 1]);
+        let area = (h * w) as f64 * rng.uniform(self.scale.0, self.scale.1);
+        let log_ratio = (self.ratio.0.ln(), self.ratio.1.ln());
+        let aspect = rng.uniform(log_ratio.0, log_ratio.1).exp();
+
+        let crop_w = i64::min(w, ((area * aspect).sqrt().round() as i64).max(1));
+        let crop_h = i64::min(h, ((area / aspect).sqrt().round() as i64).max(1));
+        let top = rng.uniform(0.0, (h - crop_h + 1) as f64) as i64;
+        let left = rng.uniform(0.0, (w - crop_w + 1) as f64) as i64;
+
+        let cropped = xs.narrow(-2, top, crop_h).narrow(-1, left, crop_w);
+        crate::vision::image::resize_filter(
+            &cropped,
+            self.size,
+            self.size,
+            crate::vision::image::ResizeFilter::Bilinear,
+        )
+    }
+}
+
+impl Transform for RandomResizedCrop {
+    fn augment(&self, xs: &Tensor, rng: &mut Rng) -> Tensor {
+        if xs.dim() == 4 {
+            let batch = xs.size()[0];
+            let samples: Vec<Tensor> =
+                (0..batch).map(|i| self.augment_one(&xs.get(i), rng)).collect();
+            Tensor::stack(&samples, 0)
+        } else {
+            self.augment_one(xs, rng)
+        }
+    }
+}
+
+/// Flips the input left-right with probability `p`.
+#[derive(Debug)]
+pub struct RandomHorizontalFlip {
+    p: f64,
+}
+
+impl RandomHorizontalFlip {
+    pub fn new(p: f64) -> RandomHorizontalFlip {
+        RandomHorizontalFlip { p }
+    }
+}
+
+impl Transform for RandomHorizontalFlip {
+    fn augment(&self, xs: &Tensor, rng: &mut Rng) -> Tensor {
+        if rng.uniform(0.0, 1.0) < self.p {
+            xs.flip(&[xs.dim() as i64 - 1])
+        } else {
+            xs.shallow_clone()
+        }
+    }
+}
+
+/// Randomly perturbs brightness, contrast, saturation and hue, each bounded
+/// by its own jitter magnitude (0 disables that perturbation).
+#[derive(Debug)]
+pub struct ColorJitter {
+    brightness: f64,
+    contrast: f64,
+    saturation: f64,
+    hue: f64,
+}
+
+impl ColorJitter {
+    pub fn new(brightness: f64, contrast: f64, saturation: f64, hue: f64) -> ColorJitter {
+        ColorJitter {
+            brightness,
+            contrast,
+            saturation,
+            hue,
+        }
+    }
+}
+
+impl Transform for ColorJitter {
+    fn augment(&self, xs: &Tensor, rng: &mut Rng) -> Tensor {
+        let mut xs = xs.shallow_clone();
+        if self.brightness > 0.0 {
+            let factor = rng.uniform(1.0 - self.brightness, 1.0 + self.brightness);
+            xs = xs * factor;
+        }
+        if self.contrast > 0.0 {
+            let factor = rng.uniform(1.0 - self.contrast, 1.0 + self.contrast);
+            let mean = xs.mean();
+            xs = (xs - &mean) * factor + mean;
+        }
+        if self.saturation > 0.0 {
+            let factor = rng.uniform(1.0 - self.saturation, 1.0 + self.saturation);
+            let channel_dim = xs.dim() as i64 - 3;
+            let channels = xs.size()[channel_dim as usize] as f64;
+            let gray = xs.sum2(&[channel_dim], true) / channels;
+            xs = (&xs - &gray) * factor + gray;
+        }
+        if self.hue > 0.0 {
+            let turns = rng.uniform(-self.hue, self.hue);
+            xs = apply_color_matrix(&xs, &hue_rotation_matrix(turns * std::f64::consts::TAU));
+        }
+        xs.clamp(0.0, 1.0)
+    }
+}
+
+/// The 3x3 matrix rotating hue by `radians` while leaving luma untouched,
+/// obtained by rotating the chroma (I, Q) plane of the YIQ color model and
+/// converting back to RGB. This is the same linear approximation used by
+/// e.g. the CSS/SVG `hue-rotate` filter, and avoids a full RGB->HSV->RGB
+/// round trip.
+fn hue_rotation_matrix(radians: f64) -> [[f64; 3]; 3] {
+    const RGB_TO_YIQ: [[f64; 3]; 3] = [
+        [0.299, 0.587, 0.114],
+        [0.596, -0.274, -0.322],
+        [0.211, -0.523, 0.312],
+    ];
+    const YIQ_TO_RGB: [[f64; 3]; 3] = [
+        [1.0, 0.956, 0.621],
+        [1.0, -0.272, -0.647],
+        [1.0, -1.106, 1.703],
+    ];
+    let (cos, sin) = (radians.cos(), radians.sin());
+    let rotate_chroma = [[1.0, 0.0, 0.0], [0.0, cos, -sin], [0.0, sin, cos]];
+    matmul3(&YIQ_TO_RGB, &matmul3(&rotate_chroma, &RGB_TO_YIQ))
+}
+
+fn matmul3(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+/// Applies a `3x3` color transform `m` to the channel dimension of a
+/// `[..., channel, height, width]` tensor, where `channel` is the 3rd from
+/// last dimension (so this works for both `[C,H,W]` and `[N,C,H,W]`).
+fn apply_color_matrix(xs: &Tensor, m: &[[f64; 3]; 3]) -> Tensor {
+    let channel_dim = xs.dim() as i64 - 3;
+    let flat: Vec<f64> = m.iter().flatten().copied().collect();
+    let matrix = Tensor::of_slice(&flat)
+        .view([3, 3])
+        .to_device(xs.device())
+        .to_kind(xs.kind());
+    let channel_last = xs.movedim(&[channel_dim], &[-1]);
+    // `m` is meant to be applied as a column-vector map, out_j = sum_i
+    // m[j][i] * x_i, i.e. `m` itself (not its transpose).
+    let transformed = Tensor::einsum("...i,ji->...j", &[&channel_last, &matrix]);
+    transformed.movedim(&[-1], &[channel_dim])
+}
+
+/// Rotates the input by a random angle in `[-degrees, degrees]`.
+#[derive(Debug)]
+pub struct RandomRotation {
+    degrees: f64,
+}
+
+impl RandomRotation {
+    pub fn new(degrees: f64) -> RandomRotation {
+        RandomRotation { degrees }
+    }
+}
+
+impl Transform for RandomRotation {
+    fn augment(&self, xs: &Tensor, rng: &mut Rng) -> Tensor {
+        let angle = rng.uniform(-self.degrees, self.degrees).to_radians();
+        let (cos, sin) = (angle.cos(), angle.sin());
+        let theta = Tensor::of_slice(&[cos, -sin, 0.0, sin, cos, 0.0])
+            .view([1, 2, 3])
+            .to_kind(xs.kind());
+        let batched = if xs.dim() == 3 { xs.unsqueeze(0) } else { xs.shallow_clone() };
+        let theta = theta.expand(&[batched.size()[0], 2, 3], false);
+        let grid = Tensor::affine_grid_generator(&theta, &batched.size(), false);
+        let rotated = batched.grid_sampler(&grid, 0, 0, false);
+        if xs.dim() == 3 {
+            rotated.squeeze_dim(0)
+        } else {
+            rotated
+        }
+    }
+}
+
+/// Normalizes the input per-channel using `(x - mean) / std`.
+#[derive(Debug)]
+pub struct Normalize {
+    mean: [f64; 3],
+    std: [f64; 3],
+}
+
+impl Normalize {
+    pub fn new(mean: [f64; 3], std: [f64; 3]) -> Normalize {
+        Normalize { mean, std }
+    }
+}
+
+impl Transform for Normalize {
+    fn augment(&self, xs: &Tensor, _rng: &mut Rng) -> Tensor {
+        crate::vision::image::normalize(xs, &self.mean, &self.std)
+    }
+}
+
+/// Chains a sequence of [`Transform`]s into a single, seedable augmentation
+/// pipeline that can be used anywhere an `nn::Module` is expected.
+#[derive(Debug)]
+pub struct Compose {
+    transforms: Vec<Box<dyn Transform>>,
+    rng: RefCell<Rng>,
+}
+
+impl Compose {
+    /// Creates a pipeline applying `transforms` in order, seeded with `seed`
+    /// for reproducibility.
+    pub fn new(transforms: Vec<Box<dyn Transform>>, seed: u64) -> Compose {
+        Compose {
+            transforms,
+            rng: RefCell::new(Rng::new(seed)),
+        }
+    }
+}
+
+impl Module for Compose {
+    fn forward(&self, xs: &Tensor) -> Tensor {
+        let mut rng = self.rng.borrow_mut();
+        self.transforms
+            .iter()
+            .fold(xs.shallow_clone(), |xs, t| t.augment(&xs, &mut rng))
+    }
+}
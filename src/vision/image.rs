@@ -4,6 +4,30 @@ use crate::utils::{path_to_str, TorchError};
 use crate::Tensor;
 use libc::c_int;
 
+/// Resampling filter used when resizing an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeFilter {
+    /// Nearest neighbour, fastest but prone to aliasing.
+    Nearest,
+    /// Bilinear interpolation, a good speed/quality default.
+    Bilinear,
+    /// Bicubic interpolation, sharper than bilinear at a higher cost.
+    Bicubic,
+    /// Lanczos (area-based) resampling, best quality for downscaling.
+    Lanczos,
+}
+
+impl ResizeFilter {
+    fn to_c_int(self) -> c_int {
+        match self {
+            ResizeFilter::Nearest => 0,
+            ResizeFilter::Bilinear => 1,
+            ResizeFilter::Bicubic => 2,
+            ResizeFilter::Lanczos => 3,
+        }
+    }
+}
+
 /// On success returns a tensor of shape [width, height, channels].
 fn load_hwc(path: &std::path::Path) -> Result<Tensor, TorchError> {
     let path = std::ffi::CString::new(path_to_str(path)?)?;
@@ -21,7 +45,21 @@ fn save_hwc(t: &Tensor, path: &std::path::Path) -> Result<(), TorchError> {
 /// Expects a tensor of shape [width, height, channels].
 /// On success returns a tensor of shape [width, height, channels].
 fn resize_hwc(t: &Tensor, out_w: i64, out_h: i64) -> Tensor {
-    let c_tensor = unsafe_torch!({ torch_sys::at_resize_image(t.c_tensor, out_w as c_int, out_h as c_int) });
+    resize_hwc_filter(t, out_w, out_h, ResizeFilter::Bilinear)
+}
+
+/// Expects a tensor of shape [height, width, channels].
+/// On success returns a tensor of shape [out_h, out_w, channels], resampled
+/// using `filter`.
+fn resize_hwc_filter(t: &Tensor, out_w: i64, out_h: i64, filter: ResizeFilter) -> Tensor {
+    let c_tensor = unsafe_torch!({
+        torch_sys::at_resize_image_filter(
+            t.c_tensor,
+            out_w as c_int,
+            out_h as c_int,
+            filter.to_c_int(),
+        )
+    });
     Tensor { c_tensor }
 }
 
@@ -50,10 +88,181 @@ pub fn save(t: &Tensor, path: &std::path::Path) -> Result<(), TorchError> {
     save_hwc(&chw_to_hwc(t), path)
 }
 
-/// Resizes an image.
+/// Resizes an image using bilinear resampling.
 ///
 /// This expects as input a tensor of shape [channel, height, width] and returns
 /// a tensor of shape [channel, out_h, out_w].
 pub fn resize(t: &Tensor, out_w: i64, out_h: i64) -> Tensor {
-    hwc_to_chw(&resize_hwc(&chw_to_hwc(t), out_w, out_h))
+    resize_filter(t, out_w, out_h, ResizeFilter::Bilinear)
+}
+
+/// Resizes an image using the given resampling filter.
+///
+/// This expects as input a tensor of shape [channel, height, width] and returns
+/// a tensor of shape [channel, out_h, out_w]. Bicubic or Lanczos filters give
+/// higher quality results than bilinear, in particular when downscaling
+/// images for super-resolution or restoration models that are sensitive to
+/// aliasing.
+pub fn resize_filter(t: &Tensor, out_w: i64, out_h: i64, filter: ResizeFilter) -> Tensor {
+    hwc_to_chw(&resize_hwc_filter(&chw_to_hwc(t), out_w, out_h, filter))
+}
+
+/// Resizes an image so that its longest side becomes `target`, preserving
+/// the aspect ratio, then center-crops or zero-pads it to a `target x
+/// target` square.
+///
+/// This expects as input a tensor of shape [channel, height, width] and
+/// returns a tensor of shape [channel, target, target].
+pub fn resize_preserve_aspect(t: &Tensor, target: i64, filter: ResizeFilter) -> Tensor {
+    let (_c, h, w) = t.size3().unwrap();
+    let scale = target as f64 / i64::max(h, w) as f64;
+    let out_h = (h as f64 * scale).round() as i64;
+    let out_w = (w as f64 * scale).round() as i64;
+    let resized = resize_filter(t, out_w, out_h, filter);
+
+    let pad_or_crop = |size: i64| -> (i64, i64) {
+        if size >= target {
+            ((size - target) / 2, 0)
+        } else {
+            (0, (target - size) / 2)
+        }
+    };
+    let (crop_h, pad_h) = pad_or_crop(out_h);
+    let (crop_w, pad_w) = pad_or_crop(out_w);
+
+    let cropped = resized
+        .narrow(1, crop_h, i64::min(out_h, target))
+        .narrow(2, crop_w, i64::min(out_w, target));
+    if out_h >= target && out_w >= target {
+        cropped
+    } else {
+        let c = cropped.size()[0];
+        let square = Tensor::zeros(&[c, target, target], (cropped.kind(), cropped.device()));
+        square
+            .narrow(1, pad_h, i64::min(out_h, target))
+            .narrow(2, pad_w, i64::min(out_w, target))
+            .copy_(&cropped);
+        square
+    }
+}
+
+/// Normalizes a `[channel, height, width]` tensor in `[0, 1]` per-channel
+/// using `(x - mean) / std`, as expected by most ImageNet-pretrained CNNs.
+pub fn normalize(t: &Tensor, mean: &[f64; 3], std: &[f64; 3]) -> Tensor {
+    let mean = Tensor::of_slice(mean)
+        .to_device(t.device())
+        .to_kind(t.kind())
+        .view([3, 1, 1]);
+    let std = Tensor::of_slice(std)
+        .to_device(t.device())
+        .to_kind(t.kind())
+        .view([3, 1, 1]);
+    (t - mean) / std
+}
+
+/// Reverses [`normalize`], mapping a standardized `[channel, height, width]`
+/// tensor back to `[0, 1]`.
+pub fn denormalize(t: &Tensor, mean: &[f64; 3], std: &[f64; 3]) -> Tensor {
+    let mean = Tensor::of_slice(mean)
+        .to_device(t.device())
+        .to_kind(t.kind())
+        .view([3, 1, 1]);
+    let std = Tensor::of_slice(std)
+        .to_device(t.device())
+        .to_kind(t.kind())
+        .view([3, 1, 1]);
+    t * std + mean
+}
+
+/// On success returns a tensor of shape [height, width, channels].
+fn load_hwc_from_memory(data: &[u8]) -> Result<Tensor, TorchError> {
+    let c_tensor = unsafe_torch_err!({
+        torch_sys::at_load_image_from_memory(data.as_ptr(), data.len())
+    });
+    Ok(Tensor { c_tensor })
+}
+
+/// Expects a tensor of shape [height, width, channels].
+fn save_hwc_to_memory(t: &Tensor, format: &str) -> Result<Vec<u8>, TorchError> {
+    let format = std::ffi::CString::new(format)?;
+    let mut data_ptr: *mut u8 = std::ptr::null_mut();
+    let mut data_len: usize = 0;
+    unsafe_torch_err!({
+        torch_sys::at_save_image_to_memory(
+            t.c_tensor,
+            format.as_ptr(),
+            &mut data_ptr,
+            &mut data_len,
+        )
+    });
+    // `data_ptr` was allocated on the C++ side (stb_image_write), so it must
+    // be freed through `at_free_buffer` rather than handed to Rust's
+    // allocator: copy it into a Rust-owned `Vec` and free the original.
+    let bytes = unsafe { std::slice::from_raw_parts(data_ptr, data_len).to_vec() };
+    unsafe { torch_sys::at_free_buffer(data_ptr) };
+    Ok(bytes)
+}
+
+/// Loads an image from an in-memory buffer, decoding jpg, png, bmp, or tga
+/// data without touching the filesystem.
+///
+/// On success returns a tensor of shape [channel, height, width].
+pub fn load_from_memory(data: &[u8]) -> Result<Tensor, TorchError> {
+    let tensor = load_hwc_from_memory(data)?;
+    Ok(hwc_to_chw(&tensor))
+}
+
+/// Encodes an image to an in-memory buffer.
+///
+/// This expects as input a tensor of shape [channel, height, width] and a
+/// target `format` (one of jpg, png, tga, bmp).
+pub fn save_to_memory(t: &Tensor, format: &str) -> Result<Vec<u8>, TorchError> {
+    save_hwc_to_memory(&chw_to_hwc(t), format)
+}
+
+/// Options controlling how many frames [`load_video`] decodes.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoOptions {
+    /// Only keep one out of every `frame_stride` decoded frames.
+    pub frame_stride: i64,
+    /// Stop decoding once `max_frames` frames have been kept, if set.
+    pub max_frames: Option<i64>,
+}
+
+impl Default for VideoOptions {
+    fn default() -> Self {
+        VideoOptions {
+            frame_stride: 1,
+            max_frames: None,
+        }
+    }
+}
+
+/// Decodes a video clip into a stacked tensor.
+///
+/// On success returns a tensor of shape [time, channel, height, width].
+pub fn load_video(path: &std::path::Path, options: VideoOptions) -> Result<Tensor, TorchError> {
+    let c_path = std::ffi::CString::new(path_to_str(path)?)?;
+    let c_tensor = unsafe_torch_err!({
+        torch_sys::at_load_video(
+            c_path.as_ptr(),
+            options.frame_stride as c_int,
+            options.max_frames.unwrap_or(-1) as c_int,
+        )
+    });
+    // The underlying frames are decoded as [time, height, width, channel].
+    let frames = Tensor { c_tensor };
+    Ok(frames.permute(&[0, 3, 1, 2]))
+}
+
+/// Encodes a stacked `[time, channel, height, width]` tensor as a video clip.
+///
+/// The output format is based on the filename suffix.
+pub fn save_video(t: &Tensor, path: &std::path::Path, fps: i64) -> Result<(), TorchError> {
+    let c_path = std::ffi::CString::new(path_to_str(path)?)?;
+    let frames = t.permute(&[0, 2, 3, 1]);
+    let _ = unsafe_torch_err!({
+        torch_sys::at_save_video(frames.c_tensor, c_path.as_ptr(), fps as c_int)
+    });
+    Ok(())
 }
\ No newline at end of file